@@ -0,0 +1,21 @@
+use cap::Cap;
+use std::alloc::System;
+
+/// Global allocator wrapped with `cap`'s allocation counter, so a runaway
+/// crawl fails fast with a clear error instead of being OOM-killed.
+/// Uncapped (`usize::MAX`) until [`set_limit`] is called.
+#[global_allocator]
+static ALLOCATOR: Cap<System> = Cap::new(System, usize::MAX);
+
+/// Sets the allocator's ceiling in bytes. Pass `usize::MAX` to disable.
+/// Errs if `bytes` is already below what's currently allocated.
+pub fn set_limit(bytes: usize) -> Result<(), &'static str> {
+    ALLOCATOR
+        .set_limit(bytes)
+        .map_err(|_| "ARXIV_SCRAPER_MAX_BYTES is below bytes already allocated")
+}
+
+/// Bytes currently allocated through the global allocator.
+pub fn allocated() -> usize {
+    ALLOCATOR.allocated()
+}
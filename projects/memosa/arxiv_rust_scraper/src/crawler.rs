@@ -0,0 +1,89 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use governor::clock::DefaultClock;
+use governor::state::{InMemoryState, NotKeyed};
+use governor::{Quota, RateLimiter};
+use reqwest::Client;
+use tokio::sync::{mpsc, Semaphore};
+
+type Limiter = RateLimiter<NotKeyed, InMemoryState, DefaultClock>;
+
+/// Crawls a queue of URLs through a bounded worker pool, gating every
+/// outgoing request through a token-bucket rate limiter so the crawl stays
+/// polite under arXiv's aggressive throttling.
+pub struct Crawler {
+    client: Client,
+    limiter: Arc<Limiter>,
+    workers: usize,
+}
+
+impl Crawler {
+    /// `requests_per_interval` requests are permitted every `interval`,
+    /// smoothed rather than allowed to burst (e.g. 1 request / 3s).
+    pub fn new(workers: usize, requests_per_interval: u32, interval: Duration) -> Self {
+        let quota = Quota::with_period(interval / requests_per_interval.max(1))
+            .expect("interval / requests_per_interval must be non-zero");
+        Crawler {
+            client: Client::new(),
+            limiter: Arc::new(RateLimiter::direct(quota)),
+            workers,
+        }
+    }
+
+    /// Streams fetched pages to `on_page` as each one completes, rather
+    /// than buffering every body in memory before processing starts. The
+    /// caller is expected to parse and emit a page's entries and then drop
+    /// it before the next one arrives, keeping peak memory bounded by the
+    /// worker pool size instead of the URL queue size.
+    ///
+    /// Pages complete in whatever order their independent HTTP requests
+    /// finish, not in `urls` order, so each call is tagged with its
+    /// position in `urls` — callers that care about input order (e.g.
+    /// assembling chapters) can sort on it themselves.
+    ///
+    /// A URL whose fetch fails is logged and dropped rather than failing
+    /// the whole crawl.
+    pub async fn crawl_stream<F>(&self, urls: Vec<String>, mut on_page: F)
+    where
+        F: FnMut(usize, String, String),
+    {
+        let (tx, mut rx) = mpsc::channel(self.workers);
+        let semaphore = Arc::new(Semaphore::new(self.workers));
+
+        for (index, url) in urls.into_iter().enumerate() {
+            let client = self.client.clone();
+            let limiter = self.limiter.clone();
+            let semaphore = semaphore.clone();
+            let tx = tx.clone();
+
+            tokio::spawn(async move {
+                let _permit = semaphore.acquire().await.expect("semaphore closed");
+                limiter.until_ready().await;
+
+                let fetched = match client.get(&url).send().await {
+                    Ok(response) => match response.text().await {
+                        Ok(body) => Some((index, url.clone(), body)),
+                        Err(err) => {
+                            eprintln!("failed to read body for {url}: {err}");
+                            None
+                        }
+                    },
+                    Err(err) => {
+                        eprintln!("failed to fetch {url}: {err}");
+                        None
+                    }
+                };
+
+                if let Some(triple) = fetched {
+                    let _ = tx.send(triple).await;
+                }
+            });
+        }
+        drop(tx);
+
+        while let Some((index, url, body)) = rx.recv().await {
+            on_page(index, url, body);
+        }
+    }
+}
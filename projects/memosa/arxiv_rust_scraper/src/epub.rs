@@ -0,0 +1,106 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::time::Duration;
+
+use epub_builder::{EpubBuilder, EpubContent, ReferenceType, ZipLibrary};
+use scraper::Html;
+
+use crate::crawler::Crawler;
+use crate::extractor;
+
+/// Escapes the handful of XHTML entities we need for plain extracted text.
+fn escape_xhtml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('\'', "&apos;")
+        .replace('"', "&quot;")
+}
+
+/// Wraps a title, body and any image URLs in a minimal XHTML chapter
+/// document.
+fn render_chapter_xhtml(title: &str, body: &str, images: &[String]) -> String {
+    let images_html: String = images
+        .iter()
+        .map(|src| format!("<img src=\"{}\" />\n", escape_xhtml(src)))
+        .collect();
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <!DOCTYPE html>\n\
+         <html xmlns=\"http://www.w3.org/1999/xhtml\">\n\
+         <head><title>{title}</title></head>\n\
+         <body>\n<h1>{title}</h1>\n<p>{body}</p>\n{images_html}</body>\n\
+         </html>",
+        title = escape_xhtml(title),
+        body = escape_xhtml(body),
+    )
+}
+
+/// Builds an EPUB for one taxonomy category: crawls each of `paper_urls`
+/// (the category's recent abstract pages), runs each through the
+/// readability extractor, and assembles the cleaned content into one
+/// chapter per paper behind a title page and generated table of contents.
+pub async fn build_epub(
+    abbr: &str,
+    taxonomy: &HashMap<String, BTreeMap<String, String>>,
+    paper_urls: Vec<String>,
+    out_path: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let crawler = Crawler::new(4, 1, Duration::from_secs(3));
+
+    let fields = taxonomy.get(abbr);
+    let name = fields
+        .and_then(|f| f.get("name"))
+        .cloned()
+        .unwrap_or_default();
+    let description = fields
+        .and_then(|f| f.get("description"))
+        .cloned()
+        .unwrap_or_default();
+
+    let mut builder = EpubBuilder::new(ZipLibrary::new()?)?;
+    builder.metadata("title", format!("arXiv {abbr}: {name}"))?;
+    builder.add_content(
+        EpubContent::new(
+            "title.xhtml",
+            render_chapter_xhtml(&format!("{abbr} \u{2014} {name}"), &description, &[]).as_bytes(),
+        )
+        .title("Title Page")
+        .reftype(ReferenceType::TitlePage),
+    )?;
+
+    // Each paper page is parsed and extracted as it arrives, dropping the
+    // fetched HTML immediately afterwards instead of holding every paper's
+    // document in memory for the whole crawl. Pages complete in whatever
+    // order their independent HTTP requests finish, so the extracted
+    // content is tagged with its original index and sorted back into
+    // `paper_urls` order before chapters are emitted.
+    let mut extracted: Vec<(usize, extractor::ExtractedContent)> = Vec::new();
+    crawler
+        .crawl_stream(paper_urls, |index, _url, body| {
+            let document = Html::parse_document(&body);
+            if let Some(content) = extractor::extract_main_content(&document) {
+                extracted.push((index, content));
+            }
+        })
+        .await;
+    extracted.sort_by_key(|(index, _)| *index);
+
+    for (chapter_index, (_, content)) in extracted.into_iter().enumerate() {
+        let chapter_title = format!("Paper {}", chapter_index + 1);
+        let chapter_path = format!("chapter_{chapter_index}.xhtml");
+        builder.add_content(
+            EpubContent::new(
+                chapter_path,
+                render_chapter_xhtml(&chapter_title, &content.text, &content.images).as_bytes(),
+            )
+            .title(chapter_title)
+            .reftype(ReferenceType::Text),
+        )?;
+    }
+
+    let mut out_file = File::create(out_path)?;
+    builder.generate(&mut out_file)?;
+    Ok(())
+}
@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+
+use ego_tree::NodeId;
+use scraper::{ElementRef, Html, Selector};
+
+/// Main content extracted from an HTML document: the cleaned text of the
+/// winning subtree (with low text-density children pruned) plus any `img`
+/// URLs collected from what's kept.
+pub struct ExtractedContent {
+    pub text: String,
+    pub images: Vec<String>,
+}
+
+/// `class`/`id` substrings that push a node's score down — boilerplate
+/// arXiv (and most sites) wrap in these.
+const NEGATIVE_PATTERNS: &[&str] = &["comment", "sidebar", "footer", "nav"];
+
+/// `class`/`id` substrings that push a node's score up — the containers
+/// that usually hold the content we actually want.
+const POSITIVE_PATTERNS: &[&str] = &["article", "content", "abstract"];
+
+/// A direct child is pruned from the winning node's cleaned text once more
+/// than half its text sits inside `<a>` links (nav menus, "cite this"
+/// boxes, etc. are almost all links).
+const MIN_TEXT_DENSITY: f64 = 0.5;
+
+/// Extracts the primary article/abstract body from an arbitrary HTML
+/// document (e.g. an arXiv `/abs/…` page), independent of any fixed page
+/// structure.
+///
+/// This is a simplified version of the Readability scoring algorithm:
+/// each candidate block node (`p`, `div`, `td`, `article`) starts from a
+/// tag-based score, earns a point per comma and a small length bonus, and
+/// is nudged by its `class`/`id` matching a negative or positive pattern.
+/// Each node's score then propagates upward — in full to its parent, at
+/// half strength to its grandparent — so a cluster of good paragraphs
+/// lifts the container around them rather than only the lone best
+/// paragraph. The highest-scoring node wins; its low-text-density
+/// children are pruned before its cleaned text and collected image URLs
+/// are returned.
+pub fn extract_main_content(document: &Html) -> Option<ExtractedContent> {
+    let candidate_selector = Selector::parse("p, div, td, article").unwrap();
+
+    let mut scores: HashMap<NodeId, f64> = HashMap::new();
+
+    for element in document.select(&candidate_selector) {
+        let text = element.text().collect::<String>();
+        if text.trim().len() < 25 {
+            continue;
+        }
+
+        let own_score = score(&element, &text);
+        propagate(&mut scores, element, own_score);
+    }
+
+    let winner_id = scores
+        .into_iter()
+        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+        .map(|(id, _)| id)?;
+
+    let winner = ElementRef::wrap(document.tree.get(winner_id)?)?;
+    Some(clean(winner))
+}
+
+/// Scores one candidate node from its tag, text and `class`/`id`.
+fn score(element: &ElementRef, text: &str) -> f64 {
+    let mut score = base_score(element.value().name());
+    score += text.matches(',').count() as f64;
+    score += ((text.trim().len() / 100) as f64).min(3.0);
+    score += class_id_score(element);
+    score
+}
+
+/// Base score awarded purely by tag name: container-like tags (`div`,
+/// `article`) are more likely to wrap the whole article than a lone `p`.
+fn base_score(tag: &str) -> f64 {
+    match tag {
+        "div" | "article" => 5.0,
+        "td" => 3.0,
+        _ => 0.0,
+    }
+}
+
+/// +25 per positive `class`/`id` pattern matched, -25 per negative one.
+fn class_id_score(element: &ElementRef) -> f64 {
+    let haystack = format!(
+        "{} {}",
+        element.value().attr("class").unwrap_or_default(),
+        element.value().attr("id").unwrap_or_default(),
+    )
+    .to_lowercase();
+
+    let mut score = 0.0;
+    for pattern in POSITIVE_PATTERNS {
+        if haystack.contains(pattern) {
+            score += 25.0;
+        }
+    }
+    for pattern in NEGATIVE_PATTERNS {
+        if haystack.contains(pattern) {
+            score -= 25.0;
+        }
+    }
+    score
+}
+
+/// Adds `own_score` to `element`'s running total, then the same amount to
+/// its parent and half that amount to its grandparent.
+fn propagate(scores: &mut HashMap<NodeId, f64>, element: ElementRef, own_score: f64) {
+    *scores.entry(element.id()).or_insert(0.0) += own_score;
+
+    let Some(parent) = element.parent().and_then(ElementRef::wrap) else {
+        return;
+    };
+    *scores.entry(parent.id()).or_insert(0.0) += own_score;
+
+    let Some(grandparent) = parent.parent().and_then(ElementRef::wrap) else {
+        return;
+    };
+    *scores.entry(grandparent.id()).or_insert(0.0) += own_score * 0.5;
+}
+
+/// Text density of a node: the fraction of its text that isn't inside an
+/// `<a>` link.
+fn text_density(element: &ElementRef) -> f64 {
+    let total_len = element.text().collect::<String>().len();
+    if total_len == 0 {
+        return 1.0;
+    }
+
+    let link_selector = Selector::parse("a").unwrap();
+    let link_len: usize = element
+        .select(&link_selector)
+        .map(|a| a.text().collect::<String>().len())
+        .sum();
+
+    1.0 - (link_len as f64 / total_len as f64)
+}
+
+/// Builds the winning node's cleaned text, dropping any direct child
+/// whose text density is below [`MIN_TEXT_DENSITY`], and collects every
+/// `img` URL found in what's kept.
+fn clean(winner: ElementRef) -> ExtractedContent {
+    let img_selector = Selector::parse("img").unwrap();
+    let mut text = String::new();
+    let mut images = Vec::new();
+
+    for child in winner.children().filter_map(ElementRef::wrap) {
+        if text_density(&child) < MIN_TEXT_DENSITY {
+            continue;
+        }
+
+        let child_text = child.text().collect::<String>();
+        if !child_text.trim().is_empty() {
+            text.push_str(child_text.trim());
+            text.push('\n');
+        }
+
+        // `select` only walks descendants, so an `img` that is itself a
+        // direct child of the winner is picked up here.
+        if child.value().name() == "img" {
+            if let Some(src) = child.value().attr("src") {
+                images.push(src.to_string());
+            }
+        }
+        for img in child.select(&img_selector) {
+            if let Some(src) = img.value().attr("src") {
+                images.push(src.to_string());
+            }
+        }
+    }
+
+    // Every child was pruned (e.g. the winner is a lone `p`): fall back to
+    // its own text rather than returning nothing.
+    if text.is_empty() {
+        text = winner.text().collect::<String>().trim().to_string();
+    }
+
+    ExtractedContent { text, images }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_the_clean_article_body() {
+        let html = include_str!("../tests/fixtures/extractor_clean.html");
+        let document = Html::parse_document(html);
+        let content = extract_main_content(&document).expect("should find a winning candidate");
+
+        assert!(content.text.contains("first paragraph of a clean article"));
+        assert!(content.text.contains("second paragraph, continuing"));
+    }
+
+    #[test]
+    fn prunes_the_link_heavy_nav_and_keeps_the_article() {
+        let html = include_str!("../tests/fixtures/extractor_noisy.html");
+        let document = Html::parse_document(html);
+        let content = extract_main_content(&document).expect("should find a winning candidate");
+
+        assert!(content.text.contains("real article paragraph"));
+        assert!(!content.text.contains("Subscribe"));
+        assert_eq!(content.images, vec!["https://example.com/figure1.png"]);
+    }
+}
@@ -1,27 +1,44 @@
-use reqwest::blocking::get;
+mod alloc_cap;
+mod crawler;
+mod epub;
+mod extractor;
+mod markdown;
+mod search;
+
+use crawler::Crawler;
+use markdown::{default_handlers, MarkdownWriter};
 use scraper::{Html, Selector};
-use serde_json;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+/// `{group -> [{abbr -> {name, description}}]}`, the shape the scraper
+/// parses the taxonomy page into. The innermost map is a `BTreeMap` so its
+/// `name`/`description` keys serialize in a stable order.
+type Taxonomy = HashMap<String, Vec<HashMap<String, BTreeMap<String, String>>>>;
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let body = get("https://arxiv.org/category_taxonomy")?.text()?;
-    let document = Html::parse_document(&body);
+/// Parses the arXiv category taxonomy page into
+/// `{group -> [{abbr -> {name, description}}]}`.
+///
+/// Pure function of the parsed document: no I/O, so it can be exercised
+/// directly against checked-in HTML fixtures.
+fn parse_taxonomy(document: &Html) -> Taxonomy {
     let header_selector = Selector::parse(".accordion-head").unwrap();
     let body_selector = Selector::parse(".accordion-body").unwrap();
+    let categories_against_desc = Selector::parse(".columns.divided").unwrap();
+    let inner_div_selector = Selector::parse("div").unwrap();
+    let h4_selector = Selector::parse("h4").unwrap();
+    let p_selector = Selector::parse("p").unwrap();
+    let span_selector = Selector::parse("span").unwrap();
 
     let header_elems = document.select(&header_selector);
     let body_elems = document.select(&body_selector);
 
-    let mut data: HashMap<String, Vec<HashMap<String, HashMap<String, String>>>> = HashMap::new();
+    let mut data: Taxonomy = HashMap::new();
 
     for (head, body) in header_elems.zip(body_elems) {
-        let categories_against_desc = Selector::parse(".columns.divided").unwrap();
-        let inner_div_selector = Selector::parse("div").unwrap();
-        let h4_selector = Selector::parse("h4").unwrap();
-        let p_selector = Selector::parse("p").unwrap();
-        let span_selector = Selector::parse("span").unwrap();
-
         for container in body.select(&categories_against_desc) {
             let mut abbr_text = String::new();
             let mut name = String::new();
@@ -42,12 +59,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         };
                     }
                     for s in inner_container.select(&span_selector) {
-                        name = s
-                            .inner_html()
+                        let raw = s.inner_html();
+                        // Falls back to the raw text for markup that isn't
+                        // wrapped in parens instead of panicking the whole
+                        // scrape over one malformed entry.
+                        name = raw
                             .strip_prefix('(')
-                            .unwrap()
-                            .strip_suffix(')')
-                            .unwrap()
+                            .and_then(|rest| rest.strip_suffix(')'))
+                            .unwrap_or(&raw)
                             .to_string();
                     }
                 } else {
@@ -57,19 +76,224 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 }
             }
 
-            let mut inner_data = HashMap::new();
+            let mut inner_data = BTreeMap::new();
             inner_data.insert("name".to_string(), name);
             inner_data.insert("description".to_string(), description);
             entry.insert(abbr_text, inner_data);
             data.entry(head.inner_html().to_string())
-                .or_insert(Vec::new())
+                .or_default()
                 .push(entry);
         }
     }
 
-    // Output is available at this gist: https://gist.github.com/Rubix982/e0eb6c035829d9691002466e02bfabaf
-    let file = File::create("out/arxiv_categories.json")?;
-    serde_json::to_writer_pretty(file, &data).expect("Failed to write to file");
+    data
+}
+
+/// Renders the same taxonomy page to Markdown via the `MarkdownWriter`
+/// handler chain, grouped under a heading per `accordion-head` section.
+fn render_markdown(document: &Html) -> String {
+    let header_selector = Selector::parse(".accordion-head").unwrap();
+    let body_selector = Selector::parse(".accordion-body").unwrap();
+    let categories_against_desc = Selector::parse(".columns.divided").unwrap();
+    let markdown_selector = Selector::parse("h4, span, p").unwrap();
+
+    let header_elems = document.select(&header_selector);
+    let body_elems = document.select(&body_selector);
+
+    let mut markdown_out = String::new();
+    let mut writer = MarkdownWriter::new(default_handlers());
+
+    for (head, body) in header_elems.zip(body_elems) {
+        markdown_out.push_str(&format!("## {}\n\n", head.inner_html()));
+        for container in body.select(&categories_against_desc) {
+            markdown_out.push_str(&writer.render(container.select(&markdown_selector)));
+        }
+    }
+
+    markdown_out
+}
+
+/// Flattens the scraped `{group -> [{abbr -> {name, description}}]}` into
+/// `{abbr -> {name, description}}`, the shape `epub::build_epub` wants
+/// when it only needs one category's fields.
+fn flatten_taxonomy(data: &Taxonomy) -> HashMap<String, BTreeMap<String, String>> {
+    data.values()
+        .flatten()
+        .flat_map(|entry| entry.iter())
+        .map(|(abbr, fields)| (abbr.clone(), fields.clone()))
+        .collect()
+}
+
+/// The output backend selected via `--format`.
+#[derive(Debug, Clone, Copy)]
+enum OutputFormat {
+    Json,
+    Markdown,
+}
+
+#[derive(Debug)]
+enum Command {
+    /// Scrape the taxonomy page and write it out in one format.
+    Scrape { format: OutputFormat },
+    /// Query the search index built by a previous scrape.
+    Search {
+        query: String,
+        group: Option<String>,
+    },
+    /// Build an EPUB for one category from its paper abstract URLs.
+    Epub {
+        abbr: String,
+        out_path: String,
+        paper_urls: Vec<String>,
+    },
+}
+
+const USAGE: &str = "usage:\n  \
+     arxiv_rust_scraper [--format json|markdown]\n  \
+     arxiv_rust_scraper search <query> [--group <group>]\n  \
+     arxiv_rust_scraper epub <abbr> <out_path> <paper_url>...";
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    args.next(); // binary name
+
+    match args.next().as_deref() {
+        None => Ok(Command::Scrape {
+            format: OutputFormat::Json,
+        }),
+        Some("--format") => {
+            let format = match args.next().as_deref() {
+                Some("json") => OutputFormat::Json,
+                Some("markdown") => OutputFormat::Markdown,
+                Some(other) => return Err(format!("unknown --format {other}\n{USAGE}")),
+                None => return Err(format!("--format requires a value\n{USAGE}")),
+            };
+            Ok(Command::Scrape { format })
+        }
+        Some("search") => {
+            let query = args.next().ok_or_else(|| USAGE.to_string())?;
+            let mut group = None;
+            if args.next().as_deref() == Some("--group") {
+                group = Some(args.next().ok_or_else(|| USAGE.to_string())?);
+            }
+            Ok(Command::Search { query, group })
+        }
+        Some("epub") => {
+            let abbr = args.next().ok_or_else(|| USAGE.to_string())?;
+            let out_path = args.next().ok_or_else(|| USAGE.to_string())?;
+            let paper_urls: Vec<String> = args.collect();
+            Ok(Command::Epub {
+                abbr,
+                out_path,
+                paper_urls,
+            })
+        }
+        Some(other) => Err(format!("unknown command {other}\n{USAGE}")),
+    }
+}
+
+/// Crawls the arXiv category taxonomy page and returns its parsed body.
+async fn fetch_taxonomy_document() -> Result<Html, Box<dyn std::error::Error>> {
+    let crawler = Crawler::new(4, 1, Duration::from_secs(3));
+    let mut fetched: Option<(String, String)> = None;
+    crawler
+        .crawl_stream(
+            vec!["https://arxiv.org/category_taxonomy".to_string()],
+            |_index, url, body| fetched = Some((url, body)),
+        )
+        .await;
+    let (_, body) = fetched.ok_or("failed to fetch the category taxonomy page")?;
+    Ok(Html::parse_document(&body))
+}
+
+async fn run_scrape(format: OutputFormat) -> Result<(), Box<dyn std::error::Error>> {
+    let document = fetch_taxonomy_document().await?;
+    let data = parse_taxonomy(&document);
+    eprintln!(
+        "parsed taxonomy, {} bytes allocated so far",
+        alloc_cap::allocated()
+    );
+
+    match format {
+        OutputFormat::Json => {
+            // Output is available at this gist: https://gist.github.com/Rubix982/e0eb6c035829d9691002466e02bfabaf
+            let file = File::create("out/arxiv_categories.json")?;
+            serde_json::to_writer_pretty(file, &data).expect("Failed to write to file");
+        }
+        OutputFormat::Markdown => {
+            let markdown_out = render_markdown(&document);
+            let mut md_file = File::create("out/arxiv_categories.md")?;
+            md_file.write_all(markdown_out.as_bytes())?;
+        }
+    }
+
+    search::build_index(&data, Path::new("out/index"))?;
+    eprintln!("index updated, {} bytes allocated", alloc_cap::allocated());
+
+    Ok(())
+}
+
+async fn run_search(query: &str, group: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let index = search::open_index(Path::new("out/index"))?;
+
+    for hit in search::query(&index, query, group)? {
+        println!("{}\t{}\t{}", hit.abbr, hit.name, hit.group);
+    }
 
     Ok(())
 }
+
+async fn run_epub(
+    abbr: &str,
+    out_path: &str,
+    paper_urls: Vec<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let document = fetch_taxonomy_document().await?;
+    let data = parse_taxonomy(&document);
+    let taxonomy = flatten_taxonomy(&data);
+
+    epub::build_epub(abbr, &taxonomy, paper_urls, out_path).await
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    if let Ok(max_bytes) = std::env::var("ARXIV_SCRAPER_MAX_BYTES") {
+        let max_bytes: usize = max_bytes
+            .parse()
+            .map_err(|_| "ARXIV_SCRAPER_MAX_BYTES must be a byte count")?;
+        alloc_cap::set_limit(max_bytes)?;
+    }
+
+    match parse_args(std::env::args())? {
+        Command::Scrape { format } => run_scrape(format).await,
+        Command::Search { query, group } => run_search(&query, group.as_deref()).await,
+        Command::Epub {
+            abbr,
+            out_path,
+            paper_urls,
+        } => run_epub(&abbr, &out_path, paper_urls).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_taxonomy_fixture() {
+        let html = include_str!("../tests/fixtures/category_taxonomy.html");
+        let document = Html::parse_document(html);
+        let data = parse_taxonomy(&document);
+        insta::assert_json_snapshot!(data);
+    }
+
+    /// A `span` missing its `(Name)` parens used to panic the whole scrape
+    /// via the `strip_prefix`/`strip_suffix` unwraps; this guards against
+    /// that regressing.
+    #[test]
+    fn parses_malformed_fixture_without_panicking() {
+        let html = include_str!("../tests/fixtures/category_taxonomy_malformed.html");
+        let document = Html::parse_document(html);
+        let data = parse_taxonomy(&document);
+        insta::assert_json_snapshot!(data);
+    }
+}
@@ -0,0 +1,88 @@
+use scraper::ElementRef;
+
+/// Knows how to turn one kind of HTML element into a chunk of Markdown.
+///
+/// `MarkdownWriter` dispatches each element to the first handler whose
+/// `should_handle` claims the element's tag name.
+pub trait HtmlHandler {
+    fn should_handle(&self, tag: &str) -> bool;
+    fn handle(&mut self, el: &ElementRef, out: &mut String);
+}
+
+/// Renders a stream of HTML elements to Markdown via a pluggable list of
+/// `HtmlHandler`s, tried in order for each element.
+pub struct MarkdownWriter {
+    handlers: Vec<Box<dyn HtmlHandler>>,
+}
+
+impl MarkdownWriter {
+    pub fn new(handlers: Vec<Box<dyn HtmlHandler>>) -> Self {
+        MarkdownWriter { handlers }
+    }
+
+    pub fn render<'a>(&mut self, elements: impl Iterator<Item = ElementRef<'a>>) -> String {
+        let mut out = String::new();
+        for el in elements {
+            let tag = el.value().name();
+            if let Some(handler) = self.handlers.iter_mut().find(|h| h.should_handle(tag)) {
+                handler.handle(&el, &mut out);
+            }
+        }
+        out
+    }
+}
+
+/// Renders `h4` elements as a Markdown heading, e.g. `#### cs.AI`.
+pub struct HeadingHandler;
+
+impl HtmlHandler for HeadingHandler {
+    fn should_handle(&self, tag: &str) -> bool {
+        tag == "h4"
+    }
+
+    fn handle(&mut self, el: &ElementRef, out: &mut String) {
+        out.push_str("#### ");
+        out.push_str(el.text().collect::<String>().trim());
+        out.push_str("\n\n");
+    }
+}
+
+/// Renders `span` elements (the taxonomy's bracketed long-name) emphasized.
+pub struct SpanHandler;
+
+impl HtmlHandler for SpanHandler {
+    fn should_handle(&self, tag: &str) -> bool {
+        tag == "span"
+    }
+
+    fn handle(&mut self, el: &ElementRef, out: &mut String) {
+        let text = el.text().collect::<String>();
+        let text = text.trim().trim_start_matches('(').trim_end_matches(')');
+        out.push('*');
+        out.push_str(text);
+        out.push_str("*\n\n");
+    }
+}
+
+/// Renders `p` elements as a plain Markdown paragraph.
+pub struct ParagraphHandler;
+
+impl HtmlHandler for ParagraphHandler {
+    fn should_handle(&self, tag: &str) -> bool {
+        tag == "p"
+    }
+
+    fn handle(&mut self, el: &ElementRef, out: &mut String) {
+        out.push_str(el.text().collect::<String>().trim());
+        out.push_str("\n\n");
+    }
+}
+
+/// The handler chain used for the arXiv taxonomy: heading, name, description.
+pub fn default_handlers() -> Vec<Box<dyn HtmlHandler>> {
+    vec![
+        Box::new(HeadingHandler),
+        Box::new(SpanHandler),
+        Box::new(ParagraphHandler),
+    ]
+}
@@ -0,0 +1,161 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use tantivy::collector::TopDocs;
+use tantivy::directory::MmapDirectory;
+use tantivy::query::{BooleanQuery, Occur, Query, QueryParser, TermQuery};
+use tantivy::schema::{
+    Facet, FacetOptions, IndexRecordOption, OwnedValue, Schema, TextFieldIndexing, TextOptions,
+    STORED, STRING,
+};
+use tantivy::{doc, Index, IndexWriter, TantivyDocument, Term};
+
+/// `{group -> [{abbr -> {name, description}}]}`, the shape the scraper
+/// parses the taxonomy page into.
+pub type Taxonomy = HashMap<String, Vec<HashMap<String, BTreeMap<String, String>>>>;
+
+/// A single ranked search hit over the taxonomy.
+pub struct Hit {
+    pub abbr: String,
+    pub name: String,
+    pub group: String,
+}
+
+/// Field handles for the taxonomy schema, kept together so callers don't
+/// have to re-derive them from the `Schema` on every call.
+pub struct TaxonomyIndex {
+    pub index: Index,
+    fields: TaxonomyFields,
+}
+
+fn build_schema() -> (Schema, TaxonomyFields) {
+    let mut builder = Schema::builder();
+    let abbr = builder.add_text_field("abbr", STRING | STORED);
+    let text_indexing = TextFieldIndexing::default()
+        .set_index_option(IndexRecordOption::WithFreqsAndPositions);
+    let text_options = TextOptions::default()
+        .set_indexing_options(text_indexing)
+        .set_stored();
+    let name = builder.add_text_field("name", text_options.clone());
+    let description = builder.add_text_field("description", text_options);
+    let group = builder.add_facet_field("group", FacetOptions::default());
+    (
+        builder.build(),
+        TaxonomyFields {
+            abbr,
+            name,
+            description,
+            group,
+        },
+    )
+}
+
+/// Field handles carved out of `build_schema`'s `Schema` for reuse when
+/// indexing and querying.
+struct TaxonomyFields {
+    abbr: tantivy::schema::Field,
+    name: tantivy::schema::Field,
+    description: tantivy::schema::Field,
+    group: tantivy::schema::Field,
+}
+
+/// Builds or updates the Tantivy index at `index_dir` from the scraped
+/// taxonomy `HashMap` of `{group -> [{abbr -> {name, description}}]}`.
+///
+/// Uses `open_or_create` rather than `create_in_dir` so re-running the
+/// scraper against an existing index directory refreshes it in place
+/// instead of erroring out.
+pub fn build_index(data: &Taxonomy, index_dir: &Path) -> tantivy::Result<TaxonomyIndex> {
+    std::fs::create_dir_all(index_dir)?;
+
+    let (schema, fields) = build_schema();
+    let directory = MmapDirectory::open(index_dir)?;
+    let index = Index::open_or_create(directory, schema)?;
+    let mut writer: IndexWriter = index.writer(50_000_000)?;
+    writer.delete_all_documents()?;
+
+    for (group_name, entries) in data {
+        let group_facet = Facet::from(&format!("/{}", group_name));
+        for entry in entries {
+            for (abbr_text, entry_fields) in entry {
+                let name_text = entry_fields.get("name").cloned().unwrap_or_default();
+                let description_text = entry_fields
+                    .get("description")
+                    .cloned()
+                    .unwrap_or_default();
+                writer.add_document(doc!(
+                    fields.abbr => abbr_text.clone(),
+                    fields.name => name_text,
+                    fields.description => description_text,
+                    fields.group => group_facet.clone(),
+                ))?;
+            }
+        }
+    }
+    writer.commit()?;
+
+    Ok(TaxonomyIndex { index, fields })
+}
+
+/// Opens the Tantivy index already built at `index_dir` by a previous
+/// `scrape` run, for a cheap local `query()` with no network access and
+/// no re-indexing.
+///
+/// `build_schema` assigns the same `Field` handles in the same order
+/// every time it's called, so the ones built here line up with the
+/// on-disk index's schema without needing to read it back.
+pub fn open_index(index_dir: &Path) -> tantivy::Result<TaxonomyIndex> {
+    let (_schema, fields) = build_schema();
+    let directory = MmapDirectory::open(index_dir)?;
+    let index = Index::open(directory)?;
+    Ok(TaxonomyIndex { index, fields })
+}
+
+/// Runs a BM25-ranked query over `name`/`description`, optionally narrowed
+/// to a single taxonomy group (e.g. `"Computer Science"`).
+pub fn query(idx: &TaxonomyIndex, query_str: &str, group_filter: Option<&str>) -> tantivy::Result<Vec<Hit>> {
+    let reader = idx.index.reader()?;
+    let searcher = reader.searcher();
+
+    let parser = QueryParser::for_index(&idx.index, vec![idx.fields.name, idx.fields.description]);
+    let text_query = parser.parse_query(query_str)?;
+
+    let query: Box<dyn Query> = match group_filter {
+        Some(group_name) => {
+            let facet_term = Term::from_facet(idx.fields.group, &Facet::from(&format!("/{}", group_name)));
+            let facet_query = TermQuery::new(facet_term, IndexRecordOption::Basic);
+            Box::new(BooleanQuery::new(vec![
+                (Occur::Must, text_query),
+                (Occur::Must, Box::new(facet_query)),
+            ]))
+        }
+        None => text_query,
+    };
+
+    let top_docs = searcher.search(&query, &TopDocs::with_limit(20))?;
+
+    let mut hits = Vec::with_capacity(top_docs.len());
+    for (_score, doc_address) in top_docs {
+        let retrieved: TantivyDocument = searcher.doc(doc_address)?;
+        hits.push(Hit {
+            abbr: field_text(&retrieved, idx.fields.abbr),
+            name: field_text(&retrieved, idx.fields.name),
+            group: field_facet(&retrieved, idx.fields.group),
+        });
+    }
+    Ok(hits)
+}
+
+fn field_text(doc: &TantivyDocument, field: tantivy::schema::Field) -> String {
+    match doc.get_first(field) {
+        Some(OwnedValue::Str(s)) => s.clone(),
+        _ => String::new(),
+    }
+}
+
+fn field_facet(doc: &TantivyDocument, field: tantivy::schema::Field) -> String {
+    match doc.get_first(field) {
+        Some(OwnedValue::Facet(f)) => f.to_string(),
+        _ => String::new(),
+    }
+}